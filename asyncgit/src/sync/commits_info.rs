@@ -66,6 +66,80 @@ impl CommitId {
 	}
 }
 
+/// a resolved two-dot (`A..B`) or three-dot (`A...B`) revision range, or
+/// a single revspec resolved to both endpoints being equal
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CommitRange {
+	/// the range's lower, excluded endpoint (`A`)
+	pub from: CommitId,
+	/// the range's upper, included endpoint (`B`)
+	pub to: CommitId,
+	/// for a three-dot (symmetric) range, the merge-base of `from` and
+	/// `to`; `None` for a two-dot range
+	pub merge_base: Option<CommitId>,
+}
+
+impl CommitRange {
+	/// resolves `spec` (e.g. `main..feature`, `main...feature`, or a
+	/// single revision) against `repo_path` using libgit2's range-mode
+	/// `revparse`
+	pub fn from_revspec(
+		repo_path: &RepoPath,
+		spec: &str,
+	) -> Result<Self> {
+		scope_time!("CommitRange::from_revspec");
+
+		let repo = repo(repo_path)?;
+		let revspec = repo.revparse(spec)?;
+
+		let from = revspec
+			.from()
+			.ok_or_else(|| {
+				crate::Error::Generic(format!(
+					"`{spec}` did not resolve to a commit",
+				))
+			})?
+			.id();
+		let to = revspec.to().map_or(from, |to| to.id());
+
+		let merge_base = if revspec
+			.mode()
+			.contains(git2::RevparseMode::MERGE_BASE)
+		{
+			Some(repo.merge_base(from, to)?.into())
+		} else {
+			None
+		};
+
+		Ok(Self {
+			from: from.into(),
+			to: to.into(),
+			merge_base,
+		})
+	}
+
+	/// lists the `CommitId`s reachable from `to` but not from `from`
+	/// (newest first), i.e. what `git log from..to` would print; for a
+	/// single revision (`from == to`) it lists everything reachable from
+	/// `to`, that commit included; this is what a log view scoped to this
+	/// range should render
+	pub fn commit_ids(&self, repo_path: &RepoPath) -> Result<Vec<CommitId>> {
+		scope_time!("CommitRange::commit_ids");
+
+		let repo = repo(repo_path)?;
+
+		let mut walk = repo.revwalk()?;
+		walk.set_sorting(git2::Sort::TIME)?;
+		walk.push(self.to.into())?;
+		if self.from != self.to {
+			walk.hide(self.from.into())?;
+		}
+
+		walk.map(|oid| Ok(CommitId::from(oid?)))
+			.collect::<Result<Vec<_>>>()
+	}
+}
+
 impl Display for CommitId {
 	fn fmt(
 		&self,
@@ -113,6 +187,186 @@ pub struct CommitInfo {
 	pub author: String,
 	///
 	pub id: CommitId,
+	/// `Some` when `message` follows the Conventional Commits spec,
+	/// `None` otherwise so the log view can still render arbitrary
+	/// commit messages
+	pub conventional: Option<ConventionalCommit>,
+}
+
+/// a single `token: value` (or `token #value`) trailer found in the
+/// footer of a commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalFooter {
+	///
+	pub token: String,
+	///
+	pub value: String,
+}
+
+/// structured view of a commit message that follows the
+/// [Conventional Commits](https://www.conventionalcommits.org) spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+	/// the commit type, e.g. `feat`, `fix`, `chore`
+	pub commit_type: String,
+	/// optional scope given in parentheses, e.g. `(parser)`
+	pub scope: Option<String>,
+	/// set when the subject carries a `!` before the colon or a
+	/// `BREAKING CHANGE`/`BREAKING-CHANGE` footer is present
+	pub breaking: bool,
+	/// the description following the `: ` on the subject line
+	pub description: String,
+	/// the free-form body between the subject and the footers
+	pub body: Option<String>,
+	/// git-trailer style footers, in order of appearance
+	pub footers: Vec<ConventionalFooter>,
+}
+
+impl ConventionalCommit {
+	/// parses `message` as a Conventional Commit, returning `None` if
+	/// the subject line does not match `<type>[(scope)][!]: <description>`
+	pub fn parse(message: &str) -> Option<Self> {
+		let mut lines = message.lines();
+		let subject = lines.next()?.trim();
+
+		let (header, description) = subject.split_once(':')?;
+		let description = description.trim();
+		if description.is_empty() {
+			return None;
+		}
+
+		let (header, breaking_bang) =
+			header.strip_suffix('!').map_or((header, false), |h| (h, true));
+
+		let (commit_type, scope) =
+			if let Some(paren_start) = header.find('(') {
+				let commit_type = &header[..paren_start];
+				let scope = header[paren_start + 1..]
+					.strip_suffix(')')?
+					.to_string();
+				(commit_type, Some(scope))
+			} else {
+				(header, None)
+			};
+
+		if commit_type.is_empty()
+			|| !commit_type
+				.chars()
+				.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+		{
+			return None;
+		}
+
+		let rest: Vec<&str> = lines.collect();
+		let (body, footers) = split_body_and_footers(&rest);
+
+		let breaking = breaking_bang
+			|| footers.iter().any(|f| {
+				f.token.eq_ignore_ascii_case("BREAKING CHANGE")
+					|| f.token.eq_ignore_ascii_case("BREAKING-CHANGE")
+			});
+
+		Some(Self {
+			commit_type: commit_type.to_string(),
+			scope,
+			breaking,
+			description: description.to_string(),
+			body,
+			footers,
+		})
+	}
+}
+
+/// splits the lines following the subject into an optional body and the
+/// trailing footer block (the last paragraph, if every line in it looks
+/// like a git trailer)
+fn split_body_and_footers(
+	lines: &[&str],
+) -> (Option<String>, Vec<ConventionalFooter>) {
+	let to_text = |ls: &[&str]| -> Option<String> {
+		let text =
+			ls.iter().map(|l| l.trim_end()).collect::<Vec<_>>().join("\n");
+		let text = text.trim();
+		(!text.is_empty()).then(|| text.to_string())
+	};
+
+	let paragraphs: Vec<&[&str]> = split_paragraphs(lines).collect();
+
+	let Some((last, rest)) = paragraphs.split_last() else {
+		return (None, Vec::new());
+	};
+
+	let footers: Vec<ConventionalFooter> =
+		last.iter().filter_map(|line| parse_footer_line(line)).collect();
+
+	if footers.len() != last.len() || footers.is_empty() {
+		return (to_text(lines), Vec::new());
+	}
+
+	let body_lines: Vec<&str> =
+		rest.iter().flat_map(|p| p.iter().copied()).collect();
+
+	(to_text(&body_lines), footers)
+}
+
+/// splits `lines` into blank-line separated paragraphs, skipping any
+/// leading empty lines
+fn split_paragraphs<'a>(
+	lines: &'a [&'a str],
+) -> impl Iterator<Item = &'a [&'a str]> {
+	let mut lines = lines;
+	while lines.first().is_some_and(|l| l.trim().is_empty()) {
+		lines = &lines[1..];
+	}
+
+	std::iter::from_fn(move || {
+		while lines.first().is_some_and(|l| l.trim().is_empty()) {
+			lines = &lines[1..];
+		}
+		if lines.is_empty() {
+			return None;
+		}
+		let end = lines
+			.iter()
+			.position(|l| l.trim().is_empty())
+			.unwrap_or(lines.len());
+		let (paragraph, remainder) = lines.split_at(end);
+		lines = remainder;
+		Some(paragraph)
+	})
+}
+
+/// parses a single git-trailer style line: `token: value` or `token #value`
+fn parse_footer_line(line: &str) -> Option<ConventionalFooter> {
+	let line = line.trim();
+
+	if let Some((token, value)) = line.split_once(": ") {
+		if is_footer_token(token) {
+			return Some(ConventionalFooter {
+				token: token.to_string(),
+				value: value.trim().to_string(),
+			});
+		}
+	}
+
+	if let Some((token, value)) = line.split_once(" #") {
+		if is_footer_token(token) {
+			return Some(ConventionalFooter {
+				token: token.to_string(),
+				value: value.trim().to_string(),
+			});
+		}
+	}
+
+	None
+}
+
+fn is_footer_token(token: &str) -> bool {
+	!token.is_empty()
+		&& (token.eq_ignore_ascii_case("BREAKING CHANGE")
+			|| token
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || c == '-'))
 }
 
 ///
@@ -134,6 +388,8 @@ pub fn get_commits_info(
 
 	let res = commits
 		.map(|c: Commit| {
+			let full_message = get_message(&c, None);
+			let conventional = ConventionalCommit::parse(&full_message);
 			let message = get_message(&c, Some(message_length_limit));
 			let author = get_author_of_commit(&c, &mailmap)
 				.name()
@@ -146,6 +402,7 @@ pub fn get_commits_info(
 				author,
 				time: c.time().seconds(),
 				id: CommitId(c.id()),
+				conventional,
 			}
 		})
 		.collect::<Vec<_>>();
@@ -167,6 +424,7 @@ pub fn get_commit_info(
 	let commit_ref = commit.decode()?;
 
 	let message = gix_get_message(&commit_ref, None);
+	let conventional = ConventionalCommit::parse(&message);
 
 	let author = commit_ref.author();
 
@@ -180,6 +438,7 @@ pub fn get_commit_info(
 		author: author.to_string(),
 		time: commit_ref.time().seconds,
 		id: commit.id().detach().into(),
+		conventional,
 	})
 }
 
@@ -221,7 +480,7 @@ pub fn gix_get_message(
 
 #[cfg(test)]
 mod tests {
-	use super::get_commits_info;
+	use super::{get_commits_info, CommitRange, ConventionalCommit};
 	use crate::{
 		error::Result,
 		sync::{
@@ -337,4 +596,144 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_conventional_commit_simple() {
+		let c = ConventionalCommit::parse("fix: correct off-by-one")
+			.unwrap();
+
+		assert_eq!(c.commit_type, "fix");
+		assert_eq!(c.scope, None);
+		assert!(!c.breaking);
+		assert_eq!(c.description, "correct off-by-one");
+		assert_eq!(c.body, None);
+		assert!(c.footers.is_empty());
+	}
+
+	#[test]
+	fn test_conventional_commit_scope_and_breaking_bang() {
+		let c =
+			ConventionalCommit::parse("feat(parser)!: rewrite grammar")
+				.unwrap();
+
+		assert_eq!(c.commit_type, "feat");
+		assert_eq!(c.scope.as_deref(), Some("parser"));
+		assert!(c.breaking);
+		assert_eq!(c.description, "rewrite grammar");
+	}
+
+	#[test]
+	fn test_conventional_commit_body_and_footers() {
+		let msg = "feat(api): add search endpoint\n\nThis adds a new /search endpoint.\n\nReviewed-by: jane\nBREAKING CHANGE: removes the old /find endpoint";
+
+		let c = ConventionalCommit::parse(msg).unwrap();
+
+		assert_eq!(c.commit_type, "feat");
+		assert_eq!(c.scope.as_deref(), Some("api"));
+		assert!(c.breaking);
+		assert_eq!(
+			c.body.as_deref(),
+			Some("This adds a new /search endpoint.")
+		);
+		assert_eq!(c.footers.len(), 2);
+		assert_eq!(c.footers[0].token, "Reviewed-by");
+		assert_eq!(c.footers[0].value, "jane");
+		assert_eq!(c.footers[1].token, "BREAKING CHANGE");
+		assert_eq!(
+			c.footers[1].value,
+			"removes the old /find endpoint"
+		);
+	}
+
+	#[test]
+	fn test_conventional_commit_not_conventional() {
+		assert!(ConventionalCommit::parse("just a regular message")
+			.is_none());
+		assert!(ConventionalCommit::parse("Fix: wrong case type")
+			.is_none());
+		assert!(ConventionalCommit::parse("fix:").is_none());
+	}
+
+	#[test]
+	fn test_commit_range_two_dot() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let c1 = commit(repo_path, "commit1").unwrap();
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let c2 = commit(repo_path, "commit2").unwrap();
+
+		let range = CommitRange::from_revspec(
+			repo_path,
+			&format!("{c1}..{c2}"),
+		)
+		.unwrap();
+
+		assert_eq!(range.from, c1);
+		assert_eq!(range.to, c2);
+		assert_eq!(range.merge_base, None);
+		assert_eq!(range.commit_ids(repo_path).unwrap(), vec![c2]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_commit_range_three_dot() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let c1 = commit(repo_path, "commit1").unwrap();
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let c2 = commit(repo_path, "commit2").unwrap();
+
+		let range = CommitRange::from_revspec(
+			repo_path,
+			&format!("{c1}...{c2}"),
+		)
+		.unwrap();
+
+		assert_eq!(range.from, c1);
+		assert_eq!(range.to, c2);
+		assert_eq!(range.merge_base, Some(c1));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_commit_range_single_revspec_includes_commit() -> Result<()> {
+		let file_path = Path::new("foo");
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		File::create(root.join(file_path))?.write_all(b"a")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let c1 = commit(repo_path, "commit1").unwrap();
+		File::create(root.join(file_path))?.write_all(b"b")?;
+		stage_add_file(repo_path, file_path).unwrap();
+		let c2 = commit(repo_path, "commit2").unwrap();
+
+		let range =
+			CommitRange::from_revspec(repo_path, &c2.to_string())
+				.unwrap();
+
+		assert_eq!(range.from, c2);
+		assert_eq!(range.to, c2);
+		assert_eq!(range.commit_ids(repo_path).unwrap(), vec![c2, c1]);
+
+		Ok(())
+	}
 }
@@ -0,0 +1,151 @@
+use super::{repository::repo, RepoPath};
+use crate::error::Result;
+use git2::Config;
+use scopetime::scope_time;
+
+/// which config file a read or write should target
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConfigScope {
+	/// `.git/config` of the repository
+	Local,
+	/// the current user's `~/.gitconfig`
+	Global,
+	/// the machine-wide config, e.g. `/etc/gitconfig`
+	System,
+}
+
+/// reads `key` from the repository's config, restricted to `scope`;
+/// returns `Ok(None)` if the key is unset in that scope
+pub fn get_config_string(
+	repo_path: &RepoPath,
+	key: &str,
+	scope: ConfigScope,
+) -> Result<Option<String>> {
+	scope_time!("get_config_string");
+
+	let config = match open_scoped_config(repo_path, scope) {
+		Ok(config) => config,
+		// no config file exists for this scope, so the key is unset
+		Err(crate::Error::Git(e))
+			if e.code() == git2::ErrorCode::NotFound =>
+		{
+			return Ok(None)
+		}
+		Err(e) => return Err(e),
+	};
+
+	match config.get_string(key) {
+		Ok(value) => Ok(Some(value)),
+		Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// writes `value` for `key` into `scope`'s config file
+pub fn set_config_string(
+	repo_path: &RepoPath,
+	key: &str,
+	value: &str,
+	scope: ConfigScope,
+) -> Result<()> {
+	scope_time!("set_config_string");
+
+	let mut config = open_scoped_config(repo_path, scope)?;
+
+	config.set_str(key, value)?;
+
+	Ok(())
+}
+
+fn open_scoped_config(
+	repo_path: &RepoPath,
+	scope: ConfigScope,
+) -> Result<Config> {
+	match scope {
+		ConfigScope::Local => {
+			let mut config = repo(repo_path)?.config()?;
+			Ok(config.open_level(git2::ConfigLevel::Local)?)
+		}
+		ConfigScope::Global => {
+			let mut config = Config::open_default()?;
+			Ok(config.open_level(git2::ConfigLevel::Global)?)
+		}
+		ConfigScope::System => {
+			let mut config = Config::open_default()?;
+			Ok(config.open_level(git2::ConfigLevel::System)?)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_config_string, set_config_string, ConfigScope};
+	use crate::{error::Result, sync::tests::repo_init_empty};
+
+	#[test]
+	fn test_local_config_roundtrip() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = &root.as_os_str().to_str().unwrap().into();
+
+		assert_eq!(
+			get_config_string(repo_path, "gitui.test", ConfigScope::Local)?,
+			None
+		);
+
+		set_config_string(
+			repo_path,
+			"gitui.test",
+			"hello",
+			ConfigScope::Local,
+		)?;
+
+		assert_eq!(
+			get_config_string(repo_path, "gitui.test", ConfigScope::Local)?,
+			Some(String::from("hello"))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_local_config_does_not_see_global() -> Result<()> {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path = &root.as_os_str().to_str().unwrap().into();
+
+		// isolate this test from the machine's real `~/.gitconfig` by
+		// pointing libgit2's global config at a throwaway file
+		let global_dir = tempfile::TempDir::new()?;
+		let global_config = global_dir.path().join(".gitconfig");
+		std::fs::write(
+			&global_config,
+			"[gitui]\n\ttest = from-global\n",
+		)?;
+		std::env::set_var("GIT_CONFIG_GLOBAL", &global_config);
+
+		let result = (|| -> Result<()> {
+			assert_eq!(
+				get_config_string(
+					repo_path,
+					"gitui.test",
+					ConfigScope::Global
+				)?,
+				Some(String::from("from-global"))
+			);
+			assert_eq!(
+				get_config_string(
+					repo_path,
+					"gitui.test",
+					ConfigScope::Local
+				)?,
+				None
+			);
+			Ok(())
+		})();
+
+		std::env::remove_var("GIT_CONFIG_GLOBAL");
+
+		result
+	}
+}
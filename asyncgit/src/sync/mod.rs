@@ -0,0 +1,14 @@
+mod commits_info;
+mod config;
+mod patch_email;
+
+pub use commits_info::{
+	get_commit_info, get_commits_info, get_message, gix_get_message,
+	CommitId, CommitInfo, CommitRange, ConventionalCommit,
+	ConventionalFooter,
+};
+pub use config::{get_config_string, set_config_string, ConfigScope};
+pub use patch_email::{
+	format_patch_emails, send_patch_emails, to_mbox, PatchEmail,
+	PatchEmailConfig, Transport,
+};
@@ -0,0 +1,649 @@
+use std::{
+	fmt::Write as _,
+	io::{BufRead, BufReader, Write as _},
+	net::TcpStream,
+	process::{Command, Stdio},
+};
+
+use super::{
+	commits_info::get_commits_info, repository::repo, CommitId, RepoPath,
+};
+use crate::error::Result;
+use scopetime::scope_time;
+
+/// a single RFC-822 `git format-patch`-style message, ready to be
+/// written out as a `.patch` file, appended to an mbox, or handed to a
+/// transport
+#[derive(Debug, Clone)]
+pub struct PatchEmail {
+	/// commit this patch was generated from
+	pub id: CommitId,
+	/// 1-based position of this patch within the series (0 for the
+	/// cover letter)
+	pub index: usize,
+	/// total number of patches in the series, cover letter excluded
+	pub count: usize,
+	/// full RFC-822 message, headers and body included
+	pub raw: String,
+}
+
+/// how a patch series should be delivered
+pub enum Transport {
+	/// spawn the user's `sendmail` binary and write the message to its
+	/// stdin
+	Sendmail,
+	/// spawn `git send-email` for the already-rendered `.patch` files
+	GitSendEmail,
+	/// speak SMTP directly to `host:port`
+	Smtp {
+		///
+		host: String,
+		///
+		port: u16,
+	},
+}
+
+/// recipients, optional cover letter and other knobs controlling how a
+/// patch series is built
+#[derive(Debug, Clone, Default)]
+pub struct PatchEmailConfig {
+	/// `To:` recipients
+	pub recipients: Vec<String>,
+	/// `Cc:` recipients
+	pub cc: Vec<String>,
+	/// subject used for an extra patch 0 summarizing the series
+	pub cover_letter: Option<String>,
+}
+
+/// turns `ids` (assumed to already be in the order they should be sent,
+/// oldest first) into one RFC-822 message per commit, plus an optional
+/// leading cover letter
+pub fn format_patch_emails(
+	repo_path: &RepoPath,
+	ids: &[CommitId],
+	config: &PatchEmailConfig,
+) -> Result<Vec<PatchEmail>> {
+	scope_time!("format_patch_emails");
+
+	if ids.is_empty() {
+		return Err(crate::Error::Generic(String::from(
+			"cannot format patches for an empty commit range",
+		)));
+	}
+
+	let infos = get_commits_info(repo_path, ids, usize::MAX)?;
+	let count = infos.len();
+
+	let mut emails = Vec::with_capacity(count + 1);
+
+	if let Some(subject) = &config.cover_letter {
+		let body = format!(
+			"*** BLURB HERE ***\n\nThis cover letter summarizes the following {count} patch(es).",
+		);
+		let raw = render_message(
+			repo_path,
+			&RenderInput {
+				from: None,
+				subject,
+				date: infos[0].time,
+				message_id_seed: &format!("cover-{}", infos[0].id),
+				recipients: config,
+				index: 0,
+				count,
+				body: &body,
+			},
+		)?;
+		emails.push(PatchEmail {
+			id: infos[0].id,
+			index: 0,
+			count,
+			raw,
+		});
+	}
+
+	let repo = repo(repo_path)?;
+
+	for (i, info) in infos.iter().enumerate() {
+		let commit = repo.find_commit(info.id.into())?;
+		let diff_text = unified_diff_of_commit(&repo, &commit)?;
+
+		let subject = info.message.lines().next().unwrap_or_default();
+		let body = format!("{}\n\n{diff_text}", commit_body(&commit));
+
+		let raw = render_message(
+			repo_path,
+			&RenderInput {
+				from: Some(&info.author),
+				subject,
+				date: info.time,
+				message_id_seed: &info.id.to_string(),
+				recipients: config,
+				index: i + 1,
+				count,
+				body: &body,
+			},
+		)?;
+
+		emails.push(PatchEmail {
+			id: info.id,
+			index: i + 1,
+			count,
+			raw,
+		});
+	}
+
+	Ok(emails)
+}
+
+/// renders `emails` as a single mbox-formatted byte stream, in order
+pub fn to_mbox(emails: &[PatchEmail]) -> String {
+	let mut mbox = String::new();
+
+	for email in emails {
+		let _ = writeln!(mbox, "From gitui Thu Jan 1 00:00:00 1970");
+		mbox.push_str(&email.raw);
+		if !email.raw.ends_with('\n') {
+			mbox.push('\n');
+		}
+		mbox.push('\n');
+	}
+
+	mbox
+}
+
+/// delivers `emails` over `transport`
+pub fn send_patch_emails(
+	emails: &[PatchEmail],
+	transport: &Transport,
+) -> Result<()> {
+	scope_time!("send_patch_emails");
+
+	match transport {
+		Transport::Sendmail => {
+			for email in emails {
+				run_piped("sendmail", &["-t"], &email.raw)?;
+			}
+			Ok(())
+		}
+		Transport::GitSendEmail => send_via_git_send_email(emails),
+		Transport::Smtp { host, port } => {
+			for email in emails {
+				send_smtp(host, *port, email)?;
+			}
+			Ok(())
+		}
+	}
+}
+
+/// `git send-email` takes patch files (or a directory of them), not a
+/// message on stdin, so each rendered email is written to a temp
+/// `NNNN-<commit id>.patch` file first, then the whole series is handed to
+/// `git send-email --confirm=never` as positional arguments
+fn send_via_git_send_email(emails: &[PatchEmail]) -> Result<()> {
+	let dir = std::env::temp_dir()
+		.join(format!("gitui-patches-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).map_err(|e| {
+		crate::Error::Generic(format!(
+			"failed to create temp dir for patches: {e}"
+		))
+	})?;
+
+	let mut paths = Vec::with_capacity(emails.len());
+	for email in emails {
+		let path =
+			dir.join(format!("{:04}-{}.patch", email.index, email.id));
+		std::fs::write(&path, &email.raw).map_err(|e| {
+			crate::Error::Generic(format!(
+				"failed to write patch file {}: {e}",
+				path.display()
+			))
+		})?;
+		paths.push(path);
+	}
+
+	let mut args =
+		vec![String::from("send-email"), String::from("--confirm=never")];
+	args.extend(paths.iter().map(|p| p.display().to_string()));
+
+	let result = run("git", &args);
+
+	let _ = std::fs::remove_dir_all(&dir);
+
+	result
+}
+
+struct RenderInput<'a> {
+	from: Option<&'a str>,
+	subject: &'a str,
+	date: i64,
+	message_id_seed: &'a str,
+	recipients: &'a PatchEmailConfig,
+	index: usize,
+	count: usize,
+	body: &'a str,
+}
+
+fn render_message(
+	repo_path: &RepoPath,
+	input: &RenderInput<'_>,
+) -> Result<String> {
+	let from = input
+		.from
+		.map_or_else(|| default_identity(repo_path), ToString::to_string);
+
+	let subject = format!(
+		"[PATCH {}/{}] {}",
+		input.index, input.count, input.subject
+	);
+
+	let mut msg = String::new();
+	let _ = writeln!(msg, "From: {from}");
+	if !input.recipients.recipients.is_empty() {
+		let _ = writeln!(msg, "To: {}", input.recipients.recipients.join(", "));
+	}
+	if !input.recipients.cc.is_empty() {
+		let _ = writeln!(msg, "Cc: {}", input.recipients.cc.join(", "));
+	}
+	let _ = writeln!(msg, "Subject: {subject}");
+	let _ = writeln!(msg, "Date: {}", format_rfc2822(input.date));
+	let _ = writeln!(
+		msg,
+		"Message-Id: <{}@gitui>",
+		input.message_id_seed
+	);
+	msg.push('\n');
+	msg.push_str(input.body);
+
+	Ok(msg)
+}
+
+fn commit_body(commit: &git2::Commit<'_>) -> String {
+	let message = String::from_utf8_lossy(commit.message_bytes());
+	message
+		.splitn(2, '\n')
+		.nth(1)
+		.unwrap_or_default()
+		.trim()
+		.to_string()
+}
+
+fn unified_diff_of_commit(
+	repo: &git2::Repository,
+	commit: &git2::Commit<'_>,
+) -> Result<String> {
+	let tree = commit.tree()?;
+	let parent_tree =
+		commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+
+	let diff = repo.diff_tree_to_tree(
+		parent_tree.as_ref(),
+		Some(&tree),
+		None,
+	)?;
+
+	let mut out = String::new();
+	diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+		if let Ok(content) = std::str::from_utf8(line.content()) {
+			match line.origin() {
+				'+' | '-' | ' ' => {
+					out.push(line.origin());
+					out.push_str(content);
+				}
+				_ => out.push_str(content),
+			}
+		}
+		true
+	})?;
+
+	Ok(out)
+}
+
+fn default_identity(repo_path: &RepoPath) -> String {
+	repo(repo_path)
+		.and_then(|repo| Ok(repo.signature()?))
+		.map_or_else(
+			|_| String::from("unknown <unknown@localhost>"),
+			|sig| {
+				format!(
+					"{} <{}>",
+					sig.name().unwrap_or("unknown"),
+					sig.email().unwrap_or("unknown@localhost")
+				)
+			},
+		)
+}
+
+const WEEKDAYS: [&str; 7] =
+	["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+	"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+	"Nov", "Dec",
+];
+
+/// renders a unix `timestamp` (assumed UTC) as an RFC-2822 date header;
+/// avoids pulling in a chrono dependency purely to stamp patch emails
+fn format_rfc2822(timestamp: i64) -> String {
+	let days = timestamp.div_euclid(86400);
+	let secs_of_day = timestamp.rem_euclid(86400);
+	let (h, m, s) =
+		(secs_of_day / 3600, (secs_of_day / 60) % 60, secs_of_day % 60);
+
+	let (year, month, day) = civil_from_days(days);
+	let weekday = WEEKDAYS[days.rem_euclid(7) as usize];
+	let month_name = MONTHS[(month - 1) as usize];
+
+	format!(
+		"{weekday}, {day} {month_name} {year} {h:02}:{m:02}:{s:02} +0000"
+	)
+}
+
+/// days-since-epoch to proleptic Gregorian (y, m, d); see Howard
+/// Hinnant's "chrono-Compatible Low-Level Date Algorithms"
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+	let z = days + 719_468;
+	let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+	let doe = z - era * 146_097;
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+	let y = yoe + era * 400;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let d = doy - (153 * mp + 2) / 5 + 1;
+	let m = if mp < 10 { mp + 3 } else { mp - 9 };
+
+	(if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+fn run(cmd: &str, args: &[String]) -> Result<()> {
+	let status = Command::new(cmd).args(args).status().map_err(|e| {
+		crate::Error::Generic(format!("failed to spawn `{cmd}`: {e}"))
+	})?;
+
+	if status.success() {
+		Ok(())
+	} else {
+		Err(crate::Error::Generic(format!("`{cmd}` exited with {status}")))
+	}
+}
+
+fn run_piped(cmd: &str, args: &[&str], stdin: &str) -> Result<()> {
+	let mut child = Command::new(cmd)
+		.args(args)
+		.stdin(Stdio::piped())
+		.spawn()
+		.map_err(|e| {
+			crate::Error::Generic(format!("failed to spawn `{cmd}`: {e}"))
+		})?;
+
+	if let Some(mut pipe) = child.stdin.take() {
+		pipe.write_all(stdin.as_bytes()).map_err(|e| {
+			crate::Error::Generic(format!("failed to write to `{cmd}`: {e}"))
+		})?;
+	}
+
+	let status = child.wait().map_err(|e| {
+		crate::Error::Generic(format!("failed to wait on `{cmd}`: {e}"))
+	})?;
+
+	if status.success() {
+		Ok(())
+	} else {
+		Err(crate::Error::Generic(format!("`{cmd}` exited with {status}")))
+	}
+}
+
+/// collects the addresses of every `To:` and `Cc:` header of a rendered
+/// message, stopping at the blank line that separates headers from the
+/// body
+fn recipients_of(raw: &str) -> Vec<String> {
+	raw.lines()
+		.take_while(|line| !line.is_empty())
+		.filter_map(|line| {
+			line.strip_prefix("To: ")
+				.or_else(|| line.strip_prefix("Cc: "))
+		})
+		.flat_map(|addrs| addrs.split(',').map(|a| a.trim().to_string()))
+		.collect()
+}
+
+fn from_of(raw: &str) -> Option<String> {
+	raw.lines()
+		.take_while(|line| !line.is_empty())
+		.find_map(|line| line.strip_prefix("From: ").map(str::to_string))
+}
+
+/// speaks a minimal SMTP dialogue (EHLO/MAIL FROM/RCPT TO/DATA/QUIT) to
+/// deliver `email` to `host:port`, erroring out on any reply other than
+/// the code(s) expected for that step
+fn send_smtp(host: &str, port: u16, email: &PatchEmail) -> Result<()> {
+	let stream = TcpStream::connect((host, port)).map_err(|e| {
+		crate::Error::Generic(format!(
+			"failed to connect to {host}:{port}: {e}"
+		))
+	})?;
+
+	let mut writer = stream.try_clone().map_err(|e| {
+		crate::Error::Generic(format!("failed to clone socket: {e}"))
+	})?;
+	let mut reader = BufReader::new(stream);
+
+	let from = from_of(&email.raw).ok_or_else(|| {
+		crate::Error::Generic(String::from(
+			"patch email is missing a `From:` header",
+		))
+	})?;
+	let recipients = recipients_of(&email.raw);
+	if recipients.is_empty() {
+		return Err(crate::Error::Generic(String::from(
+			"patch email has no `To:`/`Cc:` recipients",
+		)));
+	}
+
+	read_smtp_reply(&mut reader, &[220])?;
+
+	send_smtp_command(
+		&mut writer,
+		&mut reader,
+		&format!("EHLO {host}"),
+		&[250],
+	)?;
+	send_smtp_command(
+		&mut writer,
+		&mut reader,
+		&format!("MAIL FROM:<{}>", extract_addr(&from)),
+		&[250],
+	)?;
+	for recipient in &recipients {
+		send_smtp_command(
+			&mut writer,
+			&mut reader,
+			&format!("RCPT TO:<{}>", extract_addr(recipient)),
+			&[250, 251],
+		)?;
+	}
+	send_smtp_command(&mut writer, &mut reader, "DATA", &[354])?;
+
+	let mut data = email.raw.replace("\r\n", "\n").replace('\n', "\r\n");
+	if !data.ends_with("\r\n") {
+		data.push_str("\r\n");
+	}
+	data.push_str(".\r\n");
+	writer.write_all(data.as_bytes()).map_err(|e| {
+		crate::Error::Generic(format!("failed to send message body: {e}"))
+	})?;
+	read_smtp_reply(&mut reader, &[250])?;
+
+	send_smtp_command(&mut writer, &mut reader, "QUIT", &[221])?;
+
+	Ok(())
+}
+
+/// pulls the bare `user@host` out of a `Name <user@host>` or plain
+/// `user@host` header value
+fn extract_addr(header_value: &str) -> String {
+	header_value
+		.rsplit_once('<')
+		.and_then(|(_, rest)| rest.strip_suffix('>'))
+		.unwrap_or(header_value)
+		.trim()
+		.to_string()
+}
+
+fn send_smtp_command(
+	writer: &mut TcpStream,
+	reader: &mut BufReader<TcpStream>,
+	command: &str,
+	expect: &[u16],
+) -> Result<()> {
+	writer.write_all(format!("{command}\r\n").as_bytes()).map_err(
+		|e| {
+			crate::Error::Generic(format!(
+				"failed to send `{command}`: {e}"
+			))
+		},
+	)?;
+
+	read_smtp_reply(reader, expect)
+}
+
+fn read_smtp_reply(
+	reader: &mut BufReader<TcpStream>,
+	expect: &[u16],
+) -> Result<()> {
+	let mut line = String::new();
+
+	loop {
+		line.clear();
+		reader.read_line(&mut line).map_err(|e| {
+			crate::Error::Generic(format!(
+				"failed to read SMTP reply: {e}"
+			))
+		})?;
+
+		let code: u16 = line
+			.get(..3)
+			.and_then(|code| code.parse().ok())
+			.ok_or_else(|| {
+				crate::Error::Generic(format!(
+					"malformed SMTP reply: {line:?}"
+				))
+			})?;
+
+		// a '-' in the 4th column means more reply lines follow
+		let is_last = line.as_bytes().get(3) != Some(&b'-');
+
+		if is_last {
+			return if expect.contains(&code) {
+				Ok(())
+			} else {
+				Err(crate::Error::Generic(format!(
+					"unexpected SMTP reply: {}",
+					line.trim_end()
+				)))
+			};
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		civil_from_days, format_rfc2822, recipients_of, render_message,
+		to_mbox, PatchEmail, PatchEmailConfig, RenderInput,
+	};
+	use crate::sync::{
+		commit, stage_add_file, tests::repo_init_empty, RepoPath,
+	};
+	use std::{fs::File, io::Write, path::Path};
+
+	#[test]
+	fn test_civil_from_days_epoch() {
+		// 1970-01-01 is day 0 since the unix epoch
+		assert_eq!(civil_from_days(0), (1970, 1, 1));
+	}
+
+	#[test]
+	fn test_civil_from_days_known_dates() {
+		// 2000-03-01, a post-leap-day date chosen to exercise the
+		// era/century arithmetic around a leap year
+		assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+		// 1999-12-31, the day before 2000-01-01
+		assert_eq!(civil_from_days(10_956), (1999, 12, 31));
+	}
+
+	#[test]
+	fn test_format_rfc2822_epoch() {
+		assert_eq!(format_rfc2822(0), "Thu, 1 Jan 1970 00:00:00 +0000");
+	}
+
+	#[test]
+	fn test_format_rfc2822_midday() {
+		assert_eq!(
+			format_rfc2822(86_400 + 3_661),
+			"Fri, 2 Jan 1970 01:01:01 +0000"
+		);
+	}
+
+	#[test]
+	fn test_recipients_of_collects_to_and_cc() {
+		let raw = "From: a <a@b.c>\nTo: x@y.z, w@y.z\nCc: z@y.z\nSubject: s\n\nbody";
+
+		assert_eq!(
+			recipients_of(raw),
+			vec![
+				String::from("x@y.z"),
+				String::from("w@y.z"),
+				String::from("z@y.z")
+			]
+		);
+	}
+
+	#[test]
+	fn test_render_message_and_to_mbox() {
+		let (_td, repo) = repo_init_empty().unwrap();
+		let root = repo.path().parent().unwrap();
+		let repo_path: &RepoPath =
+			&root.as_os_str().to_str().unwrap().into();
+
+		let file_path = Path::new("foo");
+		File::create(root.join(file_path))
+			.unwrap()
+			.write_all(b"a")
+			.unwrap();
+		stage_add_file(repo_path, file_path).unwrap();
+		let id = commit(repo_path, "subject").unwrap();
+
+		let config = PatchEmailConfig {
+			recipients: vec![String::from("dev@example.com")],
+			cc: vec![],
+			cover_letter: None,
+		};
+
+		let raw = render_message(
+			repo_path,
+			&RenderInput {
+				from: Some("me <me@example.com>"),
+				subject: "add feature",
+				date: 0,
+				message_id_seed: "seed",
+				recipients: &config,
+				index: 1,
+				count: 1,
+				body: "diff content",
+			},
+		)
+		.unwrap();
+
+		assert!(raw.contains("From: me <me@example.com>"));
+		assert!(raw.contains("To: dev@example.com"));
+		assert!(raw.contains("Subject: [PATCH 1/1] add feature"));
+		assert!(raw.contains("Date: Thu, 1 Jan 1970 00:00:00 +0000"));
+		assert!(raw.contains("Message-Id: <seed@gitui>"));
+		assert!(raw.ends_with("diff content"));
+
+		let email = PatchEmail { id, index: 1, count: 1, raw };
+		let mbox = to_mbox(&[email]);
+
+		assert!(mbox.starts_with("From gitui "));
+		assert!(mbox.contains("Subject: [PATCH 1/1] add feature"));
+	}
+}
@@ -1,6 +1,6 @@
 use crate::bug_report;
 use anyhow::{anyhow, Result};
-use asyncgit::sync::RepoPath;
+use asyncgit::sync::{CommitId, CommitRange, RepoPath};
 use clap::{
 	builder::ArgPredicate, crate_authors, crate_description,
 	crate_name, Arg, Command as ClapApp,
@@ -16,6 +16,10 @@ pub struct CliArgs {
 	pub theme: PathBuf,
 	pub repo_path: RepoPath,
 	pub notify_watcher: bool,
+	pub commit_range: Option<CommitRange>,
+	/// the commits the log view should render when `commit_range` is
+	/// set, resolved eagerly so the view only has to render a list
+	pub log_scope: Option<Vec<CommitId>>,
 }
 
 pub fn process_cmdline() -> Result<CliArgs> {
@@ -48,17 +52,33 @@ pub fn process_cmdline() -> Result<CliArgs> {
 		.get_one::<String>("theme")
 		.map_or_else(|| PathBuf::from("theme.ron"), PathBuf::from);
 
-	let confpath = get_app_config_path()?;
+	let config_dir_override = arg_matches
+		.get_one::<String>("config-dir")
+		.map(PathBuf::from);
+
+	let confpath = get_app_config_path(config_dir_override)?;
 	fs::create_dir_all(&confpath)?;
 	let theme = confpath.join(arg_theme);
 
 	let notify_watcher: bool =
 		*arg_matches.get_one("watcher").unwrap_or(&false);
 
+	let commit_range = arg_matches
+		.get_one::<String>("rev")
+		.map(|spec| CommitRange::from_revspec(&repo_path, spec))
+		.transpose()?;
+
+	let log_scope = commit_range
+		.as_ref()
+		.map(|range| range.commit_ids(&repo_path))
+		.transpose()?;
+
 	Ok(CliArgs {
 		theme,
 		repo_path,
 		notify_watcher,
+		commit_range,
+		log_scope,
 	})
 }
 
@@ -111,6 +131,22 @@ fn app() -> ClapApp {
 				.long("bugreport")
 				.action(clap::ArgAction::SetTrue),
 		)
+		.arg(
+			Arg::new("config-dir")
+				.help("Set the config directory, overriding $GITUI_CONFIG_DIR and the XDG/OS default")
+				.long("config-dir")
+				.env("GITUI_CONFIG_DIR")
+				.value_name("CONFIG_DIR")
+				.num_args(1),
+		)
+		.arg(
+			Arg::new("rev")
+				.help("Scope gitui to a revision or range (e.g. `main..feature`, `main...feature`)")
+				.long("rev")
+				.alias("range")
+				.value_name("REVSPEC")
+				.num_args(1),
+		)
 		.arg(
 			Arg::new("directory")
 				.help("Set the git directory")
@@ -158,13 +194,31 @@ fn get_app_cache_path() -> Result<PathBuf> {
 	Ok(path)
 }
 
-pub fn get_app_config_path() -> Result<PathBuf> {
-	let mut path = if cfg!(target_os = "macos") {
-		dirs::home_dir().map(|h| h.join(".config"))
-	} else {
-		dirs::config_dir()
+/// resolves the directory gitui's config files (theme, key bindings, ...)
+/// live in. Priority, highest first:
+/// 1. `dir_override` (the `--config-dir` CLI flag, which clap also
+///    populates from `$GITUI_CONFIG_DIR`)
+/// 2. `$XDG_CONFIG_HOME/gitui`
+/// 3. `~/.config` on macOS, otherwise the OS default config dir
+///    (`dirs::config_dir()`, which itself already honors XDG on Linux)
+pub fn get_app_config_path(
+	dir_override: Option<PathBuf>,
+) -> Result<PathBuf> {
+	if let Some(dir) = dir_override {
+		return Ok(dir);
 	}
-	.ok_or_else(|| anyhow!("failed to find os config dir."))?;
+
+	let mut path = env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.filter(|p| p.is_absolute())
+		.or_else(|| {
+			if cfg!(target_os = "macos") {
+				dirs::home_dir().map(|h| h.join(".config"))
+			} else {
+				dirs::config_dir()
+			}
+		})
+		.ok_or_else(|| anyhow!("failed to find os config dir."))?;
 
 	path.push("gitui");
 	Ok(path)